@@ -2,13 +2,16 @@ use ansi_term::Style;
 use chrono::DateTime;
 use configparser::ini::Ini;
 use getopts::Options;
+use quick_xml::events::Event as XmlEvent;
+use quick_xml::reader::Reader as XmlReader;
 use skim::prelude::*;
+use std::collections::HashSet;
 use std::env;
 use std::fs::File;
 use std::io::{ Read, Write };
 use std::path::Path;
 use std::process::{ Command, Stdio };
-use std::sync::{ Arc, Mutex, Once, atomic::AtomicBool, mpsc };
+use std::sync::{ Arc, Mutex, Once, atomic::AtomicBool, atomic::AtomicU64, atomic::AtomicUsize, mpsc };
 use std::time::Duration;
 
 lazy_static::lazy_static! {
@@ -18,6 +21,63 @@ lazy_static::lazy_static! {
 	static ref UEBERZUG_ENABLE : AtomicBool = AtomicBool::from( true );
 	static ref UEBERZUG_INIT : Once = Once::new();
 	static ref UEBERZUG_TX : Mutex<Option<mpsc::Sender<UeberzugAction>>> = Mutex::new( None );
+
+	static ref HTTP_CLIENT : Mutex<Option<reqwest::blocking::Client>> = Mutex::new( None );
+	static ref HTTP_RETRIES : AtomicUsize = AtomicUsize::new( 0 );
+	static ref HTTP_TIMEOUT : AtomicU64 = AtomicU64::new( 10 );
+}
+
+// Falls back to an unconfigured client if called before YTCli::new has parsed http.* out of
+// the config and populated HTTP_CLIENT, so a fetch never panics over a missing client.
+fn http_client() -> reqwest::blocking::Client {
+	HTTP_CLIENT
+		.lock()
+		.expect( "Failed to lock HTTP_CLIENT" )
+		.clone()
+		.unwrap_or_else( || reqwest::blocking::Client::new() )
+}
+
+fn http_get( url : &str ) -> reqwest::Result<reqwest::blocking::Response> {
+	let retries = HTTP_RETRIES.load( Ordering::SeqCst );
+	let mut attempt = 0;
+
+	loop {
+		match http_client().get( url ).send() {
+			Ok( res ) => return Ok( res ),
+			Err( e ) if attempt < retries && !e.is_status() => {
+				attempt += 1;
+				std::thread::sleep( Duration::from_millis( 250 * attempt as u64 ) );
+			}
+			Err( e ) => return Err( e )
+		}
+	}
+}
+
+// Command::output() blocks forever on a hung child, which is exactly what a single stuck
+// yt-dlp call must not be allowed to do to the rest of a parallel feed load. Polls try_wait()
+// instead of using a dedicated timeout API so this has no extra dependency.
+fn command_output_with_timeout( cmd : &mut Command, timeout : Duration ) -> std::io::Result<std::process::Output> {
+	let mut child = cmd
+		.stdout( Stdio::piped() )
+		.stderr( Stdio::piped() )
+		.spawn()?;
+
+	let start = std::time::Instant::now();
+
+	loop {
+		if child.try_wait()?.is_some() {
+			return child.wait_with_output();
+		}
+
+		if start.elapsed() > timeout {
+			child.kill()?;
+			child.wait()?;
+
+			return Err( std::io::Error::new( std::io::ErrorKind::TimedOut, "process timed out" ) );
+		}
+
+		std::thread::sleep( Duration::from_millis( 50 ) );
+	}
 }
 
 #[derive( Clone )]
@@ -77,6 +137,22 @@ impl YTCli {
 		std::fs::create_dir_all( format!( "{}/{}", *CACHE_DIR, "feed" ) ).expect( "Failed to create cache directory" );
 		std::fs::create_dir_all( format!( "{}/{}", *CACHE_DIR, "thumb" ) ).expect( "Failed to create cache directory" );
 
+		let timeout = config.getuint( "default", "http.timeout" ).unwrap_or( Some( 10 ) ).unwrap_or( 10 );
+		let retries = config.getuint( "default", "http.retries" ).unwrap_or( Some( 0 ) ).unwrap_or( 0 );
+
+		let tls_roots = config.get( "default", "http.tls_roots" ).unwrap_or_else( || String::from( "webpki" ) );
+
+		let builder = reqwest::blocking::Client::builder().timeout( Duration::from_secs( timeout ) );
+		let builder = if tls_roots == "native" {
+			builder.use_native_tls()
+		} else {
+			builder.use_rustls_tls()
+		};
+
+		*HTTP_CLIENT.lock().expect( "Failed to lock HTTP_CLIENT" ) = Some( builder.build().expect( "Failed to build HTTP client" ) );
+		HTTP_RETRIES.store( retries as usize, Ordering::SeqCst );
+		HTTP_TIMEOUT.store( timeout, Ordering::SeqCst );
+
 		YTCli {
 			config
 		}
@@ -104,19 +180,21 @@ impl YTCli {
 				} else {
 					Some( YTTopic {
 						name: topic.clone(),
-						channels: match section {
+						sources: match section {
 							Some( v ) => {
-								v.keys().map( | channel : &String | -> YTChannel {
-									if v[channel].is_some() {
-										YTChannel {
-											id: v[channel].clone().unwrap(),
-											name: Some( channel.clone() )
-										}
+								v.keys().map( | channel : &String | -> YTSource {
+									let ( id, name ) = if v[channel].is_some() {
+										( v[channel].clone().unwrap(), Some( channel.clone() ) )
+									} else {
+										( channel.clone(), None )
+									};
+
+									if let Some( id ) = id.strip_prefix( "pl:" ) {
+										YTSource::Playlist( YTPlaylist { id: String::from( id ), name } )
+									} else if id.starts_with( "PL" ) || id.starts_with( "UU" ) {
+										YTSource::Playlist( YTPlaylist { id, name } )
 									} else {
-										YTChannel {
-											id: channel.clone(),
-											name: None
-										}
+										YTSource::Channel( YTChannel { id, name } )
 									}
 								} ).collect()
 							}
@@ -161,6 +239,66 @@ impl YTCli {
 			.unwrap_or_else( || Vec::new() )
 	}
 
+	fn feed_depth( &self, cli : Option<String> ) -> usize {
+		cli
+			.and_then( | v | v.parse().ok() )
+			.or_else( || self.config.getuint( "default", "feed.depth" ).unwrap_or( None ).map( | v | v as usize ) )
+			.unwrap_or( 0 )
+	}
+
+	fn download( &self, videos : Vec<YTVideo>, resolution : Option<String>, audio : bool ) {
+		let dir = self.config
+			.get( "default", "download.dir" )
+			.unwrap_or( format!( "{}/Downloads", *HOME_DIR ) );
+
+		std::fs::create_dir_all( &dir ).expect( "Failed to create download directory" );
+
+		let concurrency = self.config
+			.getuint( "default", "download.concurrency" )
+			.unwrap_or( Some( 1 ) )
+			.unwrap_or( 1 )
+			.max( 1 ) as usize;
+
+		for chunk in videos.chunks( concurrency ) {
+			let mut tasks = Vec::new();
+
+			for video in chunk {
+				let video = video.clone();
+				let dir = dir.clone();
+				let resolution = resolution.clone();
+
+				tasks.push( std::thread::spawn( move || {
+					let mut cmd = Command::new( "yt-dlp" );
+					cmd.arg( "-o" ).arg( format!( "{}/%(title)s.%(ext)s", dir ) );
+
+					if audio {
+						cmd.arg( "-x" ).arg( "--audio-format" ).arg( "mp3" );
+					} else if let Some( res ) = &resolution {
+						cmd.arg( "-f" ).arg( format!( "bestvideo[height<={}]+bestaudio/best[height<={}]", res, res ) );
+					}
+
+					let status = cmd.arg( video.url() ).status();
+
+					( video, status )
+				} ) );
+			}
+
+			for task in tasks {
+				match task.join() {
+					Ok( ( video, Ok( status ) ) ) if status.success() => {
+						println!( "{} {}", Style::from( ansi_term::Color::Green ).bold().paint( "✓" ), video.to_string() );
+					}
+					Ok( ( video, status ) ) => {
+						println!( "{} {} ({})", Style::from( ansi_term::Color::Red ).bold().paint( "✗" ), video.to_string(), status.map( | s | s.to_string() ).unwrap_or_else( | e | e.to_string() ) );
+					}
+					Err( _ ) => {
+						println!( "{}", Style::from( ansi_term::Color::Red ).bold().paint( "✗ yt-dlp thread panicked" ) );
+					}
+				}
+			}
+		}
+	}
+
 	fn ueberzug() {
 		let ueberzug = Command::new( "ueberzug" )
 			.arg( "layer" )
@@ -260,6 +398,31 @@ impl YTCli {
 			}
 		}
 
+		let watched_maxage = self.config
+			.getuint( "default", "watched.maxage" )
+			.unwrap_or( Some( 90 ) )
+			.unwrap_or( 90 );
+
+		let cutoff = chrono::Local::now() - chrono::Duration::days( watched_maxage as i64 );
+		let mut history = load_watched();
+
+		let stale : Vec<String> = history
+			.entries()
+			.filter_map( | ( id, entry ) | {
+				entry[ "timestamp" ]
+					.as_str()
+					.and_then( | t | DateTime::parse_from_rfc3339( t ).ok() )
+					.filter( | t | t.with_timezone( &chrono::Local ) < cutoff )
+					.map( | _ | String::from( id ) )
+			} )
+			.collect();
+
+		for id in stale {
+			history.remove( &id );
+		}
+
+		save_watched( &history );
+
 		Ok(())
 	}
 }
@@ -269,13 +432,13 @@ struct YTFeed {
 }
 
 impl YTFeed {
-	fn from_channels( channels : Vec<YTChannel> ) -> YTFeed {
+	fn from_sources( sources : Vec<YTSource>, depth : usize ) -> YTFeed {
 		let mut videos = Vec::new();
 		let mut tasks = Vec::new();
 
-		for channel in channels {
+		for source in sources {
 			tasks.push( std::thread::spawn( move || {
-				channel.videos()
+				source.videos( depth )
 			} ) );
 		}
 
@@ -291,15 +454,147 @@ impl YTFeed {
 		}
 	}
 
-	fn from_topics( topics : impl IntoIterator<Item = YTTopic> ) -> YTFeed {
-		YTFeed::from_channels( topics.into_iter().flat_map( | t | -> Vec<YTChannel> { t.channels } ).collect() )
+	fn from_channels( channels : Vec<YTChannel>, depth : usize ) -> YTFeed {
+		YTFeed::from_sources( channels.into_iter().map( YTSource::Channel ).collect(), depth )
+	}
+
+	fn from_topics( topics : impl IntoIterator<Item = YTTopic>, depth : usize ) -> YTFeed {
+		YTFeed::from_sources( topics.into_iter().flat_map( | t | -> Vec<YTSource> { t.sources } ).collect(), depth )
 	}
 }
 
+fn parse_feed_xml( xml : &str ) -> Vec<YTVideo> {
+	let mut reader = XmlReader::from_str( xml );
+	reader.config_mut().trim_text( true );
+
+	let mut stack : Vec<Vec<u8>> = Vec::new();
+	let mut out = Vec::new();
+
+	let mut id = String::new();
+	let mut title = String::new();
+	let mut author = String::new();
+	let mut description = String::new();
+	let mut timestamp = String::new();
+	let mut views : Option<u64> = None;
+	let mut rating : Option<f32> = None;
+
+	let attr = | e : &quick_xml::events::BytesStart, key : &str | -> Option<String> {
+		e.try_get_attribute( key )
+			.ok()
+			.flatten()
+			.and_then( | a | a.unescape_value().ok() )
+			.map( | v | v.into_owned() )
+	};
+
+	loop {
+		match reader.read_event() {
+			Ok( XmlEvent::Eof ) => break,
+			Ok( XmlEvent::Start( e ) ) => {
+				if e.name().as_ref() == b"entry" {
+					id.clear();
+					title.clear();
+					author.clear();
+					description.clear();
+					timestamp.clear();
+					views = None;
+					rating = None;
+				}
+
+				stack.push( e.name().as_ref().to_vec() );
+			}
+			Ok( XmlEvent::Empty( e ) ) => {
+				match e.name().as_ref() {
+					b"media:statistics" => views = attr( &e, "views" ).and_then( | v | v.parse().ok() ),
+					b"media:starRating" => rating = attr( &e, "average" ).and_then( | v | v.parse().ok() ),
+					_ => {}
+				}
+			}
+			Ok( XmlEvent::End( e ) ) => {
+				if e.name().as_ref() == b"entry" {
+					out.push( YTVideo {
+						id: id.clone(),
+						author: author.clone(),
+						title: title.clone(),
+						description: description.clone(),
+						timestamp: DateTime::parse_from_rfc3339( &timestamp )
+							.expect( "Invalid feed provided" )
+							.with_timezone( &chrono::Local ),
+						views,
+						rating,
+						live_status: LiveStatus::Normal,
+						watched: false
+					} );
+				}
+
+				stack.pop();
+			}
+			Ok( XmlEvent::Text( e ) ) => {
+				let text = e.unescape().unwrap_or_default().into_owned();
+
+				match stack.last().map( | t | t.as_slice() ) {
+					Some( b"yt:videoId" ) => id = text,
+					Some( b"title" ) => title = text,
+					Some( b"name" ) => author = text,
+					Some( b"media:description" ) => description = text,
+					Some( b"published" ) => timestamp = text,
+					_ => {}
+				}
+			}
+			Ok( _ ) => {}
+			Err( e ) => panic!( "Failed to parse feed: {}", e )
+		}
+	}
+
+	out
+}
+
 #[derive( Clone )]
 struct YTTopic {
 	name : String,
-	channels : Vec<YTChannel>
+	sources : Vec<YTSource>
+}
+
+#[derive( Clone )]
+enum YTSource {
+	Channel( YTChannel ),
+	Playlist( YTPlaylist )
+}
+
+impl YTSource {
+	fn id( &self ) -> &str {
+		match self {
+			YTSource::Channel( c ) => &c.id,
+			YTSource::Playlist( p ) => &p.id
+		}
+	}
+
+	fn name( &self ) -> Option<String> {
+		match self {
+			YTSource::Channel( c ) => c.name(),
+			YTSource::Playlist( p ) => p.name()
+		}
+	}
+
+	fn videos( &self, depth : usize ) -> Vec<YTVideo> {
+		match self {
+			YTSource::Channel( c ) => {
+				let mut videos = c.videos();
+
+				if depth > videos.len() {
+					let mut seen : HashSet<String> = videos.iter().map( | v | v.id.clone() ).collect();
+
+					for video in c.deep_videos( depth ) {
+						if seen.insert( video.id.clone() ) {
+							videos.push( video );
+						}
+					}
+				}
+
+				videos
+			}
+			YTSource::Playlist( p ) => p.videos()
+		}
+	}
 }
 
 #[derive( Clone )]
@@ -314,71 +609,322 @@ impl YTChannel {
 			return self.name.clone();
 		}
 
-		let pathstr = format!( "{}/feed/{}.json", *CACHE_DIR, self.id );
+		let pathstr = format!( "{}/feed/{}.xml", *CACHE_DIR, self.id );
 		let path = Path::new( &pathstr );
 
 		if path.exists() {
 			let mut feedraw = String::new();
 			let mut file = File::open( &path ).expect( "Failed to open file" );
-			file.read_to_string( &mut feedraw ).expect( "Failed to read xq results" );
-			let feed = json::parse( &feedraw ).expect( "Invalid JSON provided" );
+			file.read_to_string( &mut feedraw ).expect( "Failed to read feed" );
+
+			return parse_feed_xml( &feedraw ).first().map( | v | v.author.clone() );
+		}
+
+		None
+	}
+
+	fn videos( &self ) -> Vec<YTVideo> {
+		let pathstr = format!( "{}/feed/{}.xml", *CACHE_DIR, self.id );
+		let path = Path::new( &pathstr );
+
+		if !path.exists() || path.metadata().expect( "Failed to retreive cache metadata" ).modified().unwrap().elapsed().unwrap() > Duration::from_secs( 1800 ) {
+			let res = http_get( &format!( "https://www.youtube.com/feeds/videos.xml?channel_id={}", self.id ) ).unwrap();
+			let mut file = File::create( &path ).expect( "Failed to create file" );
+
+			file.write( &res.bytes().expect( "Failed to retreive request content" ) ).expect( "Failed to write feed cache" );
+		}
+
+		let mut feedraw = String::new();
+		let mut file = File::open( &path ).expect( "Failed to open file" );
+		file.read_to_string( &mut feedraw ).expect( "Failed to read feed" );
+
+		let mut videos = parse_feed_xml( &feedraw );
+		detect_live_status( &self.id, &mut videos );
 
-			if feed["feed"].members().len() > 0 {
-				let author = feed["feed"][0]["author"].as_str();
+		videos
+	}
+
+	// videos.xml only ever returns the latest ~15 uploads; walking the uploads playlist
+	// (UC -> UU) through InnerTube continuations lets us reach further back.
+	fn deep_videos( &self, depth : usize ) -> Vec<YTVideo> {
+		let uploads_id = format!( "UU{}", self.id.trim_start_matches( "UC" ) );
+		let client = http_client();
+
+		let mut videos = Vec::new();
+		let mut continuation : Option<String> = None;
 
-				if author.is_some() {
-					return Some( String::from( author.unwrap_or_default() ) );
+		loop {
+			if videos.len() >= depth {
+				break;
+			}
+
+			let body = match &continuation {
+				Some( token ) => json::object! {
+					context: { client: { clientName: "WEB", clientVersion: "2.20240101" } },
+					continuation: token.as_str()
+				},
+				None => json::object! {
+					context: { client: { clientName: "WEB", clientVersion: "2.20240101" } },
+					browseId: uploads_id.as_str()
 				}
+			};
+
+			let res = match client
+				.post( "https://www.youtube.com/youtubei/v1/browse" )
+				.header( "Content-Type", "application/json" )
+				.body( body.dump() )
+				.send()
+			{
+				Ok( res ) => res,
+				Err( _ ) => break
+			};
+
+			let parsed = match res.text().ok().and_then( | t | json::parse( &t ).ok() ) {
+				Some( v ) => v,
+				None => break
+			};
+
+			let items = if continuation.is_none() {
+				&parsed[ "contents" ][ "twoColumnBrowseResultsRenderer" ][ "tabs" ][ 0 ]
+					[ "tabRenderer" ][ "content" ][ "sectionListRenderer" ][ "contents" ][ 0 ]
+					[ "itemSectionRenderer" ][ "contents" ][ 0 ][ "playlistVideoListRenderer" ][ "contents" ]
+			} else {
+				&parsed[ "onResponseReceivedActions" ][ 0 ][ "appendContinuationItemsAction" ][ "continuationItems" ]
+			};
+
+			let mut next_continuation = None;
+
+			for item in items.members() {
+				if let Some( token ) = item[ "continuationItemRenderer" ][ "continuationEndpoint" ][ "continuationCommand" ][ "token" ].as_str() {
+					next_continuation = Some( String::from( token ) );
+					continue;
+				}
+
+				let renderer = &item[ "playlistVideoRenderer" ];
+				let id = match renderer[ "videoId" ].as_str() {
+					Some( id ) => String::from( id ),
+					None => continue
+				};
+
+				videos.push( YTVideo {
+					id,
+					title: renderer[ "title" ][ "runs" ][ 0 ][ "text" ].as_str().unwrap_or_default().to_string(),
+					author: self.name().unwrap_or_default(),
+					description: String::new(),
+					timestamp: parse_relative_time( renderer[ "publishedTimeText" ][ "simpleText" ].as_str().unwrap_or_default() ),
+					views: None,
+					rating: None,
+					live_status: LiveStatus::Normal,
+					watched: false
+				} );
+
+				if videos.len() >= depth {
+					break;
+				}
+			}
+
+			continuation = next_continuation;
+
+			if continuation.is_none() {
+				break;
 			}
 		}
 
+		videos
+	}
+}
+
+fn parse_relative_time( text : &str ) -> DateTime<chrono::Local> {
+	let now = chrono::Local::now();
+	let mut parts = text.split_whitespace();
+
+	let amount = parts.find_map( | t | t.parse::<i64>().ok() ).unwrap_or( 0 );
+	let unit = parts.next().unwrap_or_default();
+
+	let delta = if unit.starts_with( "second" ) {
+		chrono::Duration::seconds( amount )
+	} else if unit.starts_with( "minute" ) {
+		chrono::Duration::minutes( amount )
+	} else if unit.starts_with( "hour" ) {
+		chrono::Duration::hours( amount )
+	} else if unit.starts_with( "day" ) {
+		chrono::Duration::days( amount )
+	} else if unit.starts_with( "week" ) {
+		chrono::Duration::weeks( amount )
+	} else if unit.starts_with( "month" ) {
+		chrono::Duration::days( amount * 30 )
+	} else if unit.starts_with( "year" ) {
+		chrono::Duration::days( amount * 365 )
+	} else {
+		chrono::Duration::zero()
+	};
+
+	now - delta
+}
+
+#[derive( Clone )]
+struct YTPlaylist {
+	id : String,
+	name : Option<String>
+}
+
+impl YTPlaylist {
+	fn name( &self ) -> Option<String> {
+		if self.name.is_some() {
+			return self.name.clone();
+		}
+
+		let pathstr = format!( "{}/feed/pl-{}.xml", *CACHE_DIR, self.id );
+		let path = Path::new( &pathstr );
+
+		if path.exists() {
+			let mut feedraw = String::new();
+			let mut file = File::open( &path ).expect( "Failed to open file" );
+			file.read_to_string( &mut feedraw ).expect( "Failed to read feed" );
+
+			return parse_feed_xml( &feedraw ).first().map( | v | v.author.clone() );
+		}
+
 		None
 	}
 
 	fn videos( &self ) -> Vec<YTVideo> {
-		let pathstr = format!( "{}/feed/{}.json", *CACHE_DIR, self.id );
+		let pathstr = format!( "{}/feed/pl-{}.xml", *CACHE_DIR, self.id );
 		let path = Path::new( &pathstr );
 
 		if !path.exists() || path.metadata().expect( "Failed to retreive cache metadata" ).modified().unwrap().elapsed().unwrap() > Duration::from_secs( 1800 ) {
-			let res = reqwest::blocking::get( &format!( "https://www.youtube.com/feeds/videos.xml?channel_id={}", self.id ) ).unwrap();
-			let file = File::create( &path ).expect( "Failed to create file" );
-
-			let mut xq = Command::new( "xq" )
-				.arg( "{ FEEDVERSION: 1, feed: [ .feed.entry[] | { id: .[\"yt:videoId\"], title: .title, author: .author.name, description: .[\"media:group\"][\"media:description\"], timestamp: .published } ] }" )
-				.stdin( Stdio::piped() )
-				.stdout( Stdio::from( file ) )
-				.spawn()
-				.expect( "xq failed to start" );
-
-			xq.stdin
-				.take()
-				.expect( "Failed to open xq's stdin" )
-				.write( &res.bytes().expect( "Failed to retreive request content" ) )
-				.expect( "Failed to write to xq's stdin" );
+			let res = http_get( &format!( "https://www.youtube.com/feeds/videos.xml?playlist_id={}", self.id ) ).unwrap();
+			let mut file = File::create( &path ).expect( "Failed to create file" );
 
-			xq.wait().expect( "xq failed" );
+			file.write( &res.bytes().expect( "Failed to retreive request content" ) ).expect( "Failed to write feed cache" );
 		}
 
 		let mut feedraw = String::new();
 		let mut file = File::open( &path ).expect( "Failed to open file" );
-		file.read_to_string( &mut feedraw ).expect( "Failed to read xq results" );
-
-		let feed = json::parse( &feedraw ).expect( "Invalid JSON provided" );
-		let mut out : Vec<YTVideo> = Vec::new();
-
-		for video in feed["feed"].members() {
-			out.push( YTVideo {
-				id: String::from( video["id"].as_str().expect( "Invalid JSON provided" ) ),
-				author: String::from( video["author"].as_str().expect( "Invalid JSON provided" ) ),
-				title: String::from( video["title"].as_str().expect( "Invalid JSON provided" ) ),
-				description: String::from( video["description"].as_str().expect( "Invalid JSON provided" ) ),
-				timestamp: DateTime::parse_from_rfc3339(
-					video["timestamp"].as_str().expect( "Invalid JSON provided" )
-				).expect( "Invalid JSON provided" ).with_timezone( &chrono::Local.clone() )
-			} )
+		file.read_to_string( &mut feedraw ).expect( "Failed to read feed" );
+
+		let mut videos = parse_feed_xml( &feedraw );
+		detect_live_status( &format!( "pl-{}", self.id ), &mut videos );
+
+		videos
+	}
+}
+
+fn live_status_from_json( v : &json::JsonValue ) -> LiveStatus {
+	if v["is_live"].as_bool().unwrap_or( false ) || v["live_status"].as_str() == Some( "is_live" ) {
+		LiveStatus::Live
+	} else if v["live_status"].as_str() == Some( "is_upcoming" ) {
+		match v["release_timestamp"].as_i64() {
+			Some( ts ) => DateTime::from_timestamp( ts, 0 )
+				.map( | t | LiveStatus::Upcoming( t.with_timezone( &chrono::Local ) ) )
+				.unwrap_or( LiveStatus::Normal ),
+			None => LiveStatus::Normal
 		}
+	} else {
+		LiveStatus::Normal
+	}
+}
+
+// RSS entries don't carry live/upcoming status; `media:statistics` is only present once a
+// video has actual view counts, so its absence is our signal to go ask yt-dlp.
+// Same 1800s window as the feed cache: live/upcoming status is exactly the kind of thing
+// that changes (a premiere starts, a stream ends), so a stale entry must be re-queried
+// rather than trusted forever.
+const LIVE_STATUS_TTL : i64 = 1800;
+
+fn detect_live_status( channel_id : &str, videos : &mut Vec<YTVideo> ) {
+	let pathstr = format!( "{}/feed/{}.live.json", *CACHE_DIR, channel_id );
+	let path = Path::new( &pathstr );
+
+	let mut cache = if path.exists() {
+		let mut raw = String::new();
+		File::open( &path ).expect( "Failed to open file" ).read_to_string( &mut raw ).expect( "Failed to read live cache" );
+		json::parse( &raw ).unwrap_or_else( | _ | json::JsonValue::new_object() )
+	} else {
+		json::JsonValue::new_object()
+	};
+
+	let mut dirty = false;
+
+	for video in videos.iter_mut() {
+		if video.views.is_some() {
+			continue;
+		}
+
+		let cached = &cache[ video.id.as_str() ];
+		let fresh = cached.is_object()
+			&& cached[ "checked_at" ]
+				.as_str()
+				.and_then( | t | DateTime::parse_from_rfc3339( t ).ok() )
+				.map( | t | chrono::Local::now() - t.with_timezone( &chrono::Local ) < chrono::Duration::seconds( LIVE_STATUS_TTL ) )
+				.unwrap_or( false );
+
+		if fresh {
+			video.live_status = live_status_from_json( cached );
+			continue;
+		}
+
+		let raw = command_output_with_timeout(
+			Command::new( "yt-dlp" ).arg( "-j" ).arg( video.url() ),
+			Duration::from_secs( HTTP_TIMEOUT.load( Ordering::SeqCst ) )
+		);
+
+		if let Ok( raw ) = raw {
+			if let Ok( info ) = json::parse( &String::from_utf8_lossy( &raw.stdout ) ) {
+				cache[ video.id.as_str() ] = json::object! {
+					live_status: info["live_status"].as_str(),
+					is_live: info["is_live"].as_bool(),
+					release_timestamp: info["release_timestamp"].as_i64(),
+					checked_at: chrono::Local::now().to_rfc3339()
+				};
+
+				video.live_status = live_status_from_json( &cache[ video.id.as_str() ] );
+				dirty = true;
+			}
+		}
+	}
+
+	if dirty {
+		File::create( &path )
+			.and_then( | mut f | f.write_all( cache.dump().as_bytes() ) )
+			.expect( "Failed to write live cache" );
+	}
+}
+
+#[derive( Clone, PartialEq )]
+enum LiveStatus {
+	Normal,
+	Live,
+	Upcoming( DateTime<chrono::Local> )
+}
+
+impl LiveStatus {
+	// Plain, unstyled text — used anywhere the caller applies its own styling on top
+	// (e.g. dimming a whole watched line), so we never nest one ansi_term string inside another.
+	fn label( &self ) -> Option<String> {
+		match self {
+			LiveStatus::Normal => None,
+			LiveStatus::Live => Some( String::from( "● LIVE" ) ),
+			LiveStatus::Upcoming( at ) => {
+				let delta = *at - chrono::Local::now();
+
+				if delta.num_seconds() <= 0 {
+					Some( String::from( "● LIVE" ) )
+				} else {
+					Some( format!( "⏳ Premieres in {}h{}m", delta.num_hours(), delta.num_minutes() % 60 ) )
+				}
+			}
+		}
+	}
+
+	fn badge( &self ) -> Option<String> {
+		let color = match self {
+			LiveStatus::Normal => return None,
+			LiveStatus::Upcoming( at ) if ( *at - chrono::Local::now() ).num_seconds() > 0 => ansi_term::Color::Yellow,
+			_ => ansi_term::Color::Red
+		};
 
-		out
+		self.label().map( | l | Style::from( color ).bold().paint( l ).to_string() )
 	}
 }
 
@@ -388,7 +934,11 @@ struct YTVideo {
 	title : String,
 	author : String,
 	description : String,
-	timestamp : DateTime<chrono::Local>
+	timestamp : DateTime<chrono::Local>,
+	views : Option<u64>,
+	rating : Option<f32>,
+	live_status : LiveStatus,
+	watched : bool
 }
 
 impl YTVideo {
@@ -405,7 +955,7 @@ impl YTVideo {
 		if !path.exists() {
 			UeberzugAction::Remove.send().expect( "Failed to send data to ueberzug" );
 
-			let res = reqwest::blocking::get( &format!( "https://i.ytimg.com/vi/{}/hq720.jpg", self.id ) ).unwrap();
+			let res = http_get( &format!( "https://i.ytimg.com/vi/{}/hq720.jpg", self.id ) ).unwrap();
 			let mut file = File::create( &path ).expect( "Failed to create file" );
 
 			file.write( &res.bytes().unwrap() ).unwrap();
@@ -436,13 +986,24 @@ impl SkimItem for YTVideo {
 			textoffset = ( 0..=( context.width / ( 1280 / 720 ) / 4 ) ).map( |_| "\n" ).collect();
 		}
 
+		let stats = match ( self.views, self.rating ) {
+			( Some( views ), Some( rating ) ) => format!( " ({} views, {:.1}★)", views, rating ),
+			( Some( views ), None ) => format!( " ({} views)", views ),
+			( None, Some( rating ) ) => format!( " ({:.1}★)", rating ),
+			( None, None ) => String::new()
+		};
+
+		let badge = self.live_status.badge().map( | b | format!( "{}\n", b ) ).unwrap_or_default();
+
         ItemPreview::AnsiText(
 			format!(
-				"{}{}\n{} | {}\n\n{}",
+				"{}{}{}\n{} | {}{}\n\n{}",
 				textoffset,
+				badge,
 				bold.paint( self.title.clone() ),
 				bold.paint( self.author.clone() ),
 				self.timestamp.format( "%Y-%m-%d %H:%M:%S" ),
+				stats,
 				self.description
 			)
 		)
@@ -451,7 +1012,55 @@ impl SkimItem for YTVideo {
 
 impl ToString for YTVideo {
 	fn to_string( &self ) -> String {
-		format!( "[{}] {}", self.author, self.title )
+		if self.watched {
+			let label = self.live_status.label().map( | l | format!( "{} ", l ) ).unwrap_or_default();
+			let text = format!( "{}[{}] {}", label, self.author, self.title );
+
+			format!( "✓ {}", Style::new().dimmed().paint( text ) )
+		} else {
+			let badge = self.live_status.badge().map( | b | format!( "{} ", b ) ).unwrap_or_default();
+
+			format!( "{}[{}] {}", badge, self.author, self.title )
+		}
+	}
+}
+
+fn watched_path() -> String {
+	format!( "{}/watched.json", *CACHE_DIR )
+}
+
+fn load_watched() -> json::JsonValue {
+	let path = Path::new( &watched_path() );
+
+	if path.exists() {
+		let mut raw = String::new();
+		File::open( &path )
+			.and_then( | mut f | f.read_to_string( &mut raw ) )
+			.expect( "Failed to read watched history" );
+
+		json::parse( &raw ).unwrap_or_else( | _ | json::JsonValue::new_object() )
+	} else {
+		json::JsonValue::new_object()
+	}
+}
+
+fn save_watched( history : &json::JsonValue ) {
+	File::create( watched_path() )
+		.and_then( | mut f | f.write_all( history.dump().as_bytes() ) )
+		.expect( "Failed to write watched history" );
+}
+
+fn mark_watched( id : &str ) {
+	let mut history = load_watched();
+	history[ id ] = json::object! { timestamp: chrono::Local::now().to_rfc3339() };
+	save_watched( &history );
+}
+
+fn annotate_watched( feed : &mut YTFeed ) {
+	let history = load_watched();
+
+	for video in feed.videos.iter_mut() {
+		video.watched = history.has_key( &video.id );
 	}
 }
 
@@ -466,6 +1075,11 @@ fn main() {
 	opts.optflag( "L", "list-topics", "lists subscribed topics" );
 	opts.optopt( "t", "topics", "show videos only from listed TOPICS", "TOPICS" );
 	opts.optopt( "", "load-subs", "load subscriptions from google takeout json", "FILE" );
+	opts.optflag( "d", "download", "download selected videos with yt-dlp instead of streaming them" );
+	opts.optopt( "", "resolution", "maximum video resolution to download", "HEIGHT" );
+	opts.optflag( "", "audio", "download audio only" );
+	opts.optopt( "", "depth", "fetch at least N videos per channel beyond the RSS feed's limit", "N" );
+	opts.optflag( "", "unwatched", "hide videos already marked as watched" );
 
 	let matches = match opts.parse( &args[1..] ) {
 		Ok( m ) => { m }
@@ -473,6 +1087,7 @@ fn main() {
 	};
 
 	let mut feed = None;
+	let depth = ytcli.feed_depth( matches.opt_str( "depth" ) );
 
 	if matches.opt_present( "h" ) {
 		print!( "{}", opts.usage( "yt-cli (https://github.com/lkucharczyk/yt-cli)" ) );
@@ -486,16 +1101,16 @@ fn main() {
 		for topic in topics {
 			println!( "{}", topic.name );
 
-			let mut channels = topic.channels;
-			channels.sort_by_cached_key( | c | { c.name().unwrap_or( "~".to_string() + &c.id ) } );
+			let mut sources = topic.sources;
+			sources.sort_by_cached_key( | s | { s.name().unwrap_or( "~".to_string() + s.id() ) } );
 
-			for channel in channels {
-				let name = channel.name();
+			for source in sources {
+				let name = source.name();
 
 				if name.is_some() {
-					println!( "  {} ({})", name.unwrap_or_default(), channel.id );
+					println!( "  {} ({})", name.unwrap_or_default(), source.id() );
 				} else {
-					println!( "  {}", channel.id );
+					println!( "  {}", source.id() );
 				}
 			}
 
@@ -510,7 +1125,7 @@ fn main() {
 		topics.sort_by_cached_key( | t | { t.name.clone() } );
 
 		for topic in topics {
-			println!( "{} ({} channels)", topic.name, topic.channels.len() );
+			println!( "{} ({} channels)", topic.name, topic.sources.len() );
 		}
 
 		return;
@@ -528,40 +1143,58 @@ fn main() {
 				name: None
 			} )
 		}
-		feed = Some( YTFeed::from_channels( subs ) );
+		feed = Some( YTFeed::from_channels( subs, depth ) );
 	}
 
 	if feed.is_none(){
-		feed = Some( YTFeed::from_topics( ytcli.topics( matches.opt_str( "t" ).unwrap_or_default() ) ) );
+		feed = Some( YTFeed::from_topics( ytcli.topics( matches.opt_str( "t" ).unwrap_or_default() ), depth ) );
+	}
+	let mut feed = feed.unwrap();
+	annotate_watched( &mut feed );
+
+	if matches.opt_present( "unwatched" ) {
+		feed.videos.retain( | v | !v.watched );
 	}
-	let feed = feed.unwrap();
+
 	if feed.videos.len() == 0 {
 		println!( "There are no videos available." );
 		return;
 	}
 
+	let download = matches.opt_present( "d" );
+	let resolution = matches.opt_str( "resolution" );
+	let audio = matches.opt_present( "audio" );
+
 	loop {
 		let out = ytcli.skim( &feed );
 
 		if out.len() > 0 {
-			UeberzugAction::Remove.send().expect( "Failed to send data to ueberzug" );
-
-			Command::new( "mpv" )
-				.arg( "--fullscreen" )
-				.args(
-					out.iter().map( | v | {
-						use std::ops::Deref;
-						v.deref()
-							.as_any()
-							.downcast_ref::<YTVideo>()
-							.expect( &format!( "Failed to retreive \"{}\"'s url", v.text() ) )
-							.url()
-					} )
-				)
-				.spawn()
-				.expect( "Failed to start mpv" )
-				.wait()
-				.expect( "Failed to wait for mpv" );
+			let videos = out.iter().map( | v | {
+				use std::ops::Deref;
+				v.deref()
+					.as_any()
+					.downcast_ref::<YTVideo>()
+					.expect( &format!( "Failed to retreive \"{}\"'s video", v.text() ) )
+					.clone()
+			} ).collect::<Vec<YTVideo>>();
+
+			if download {
+				ytcli.download( videos, resolution.clone(), audio );
+			} else {
+				UeberzugAction::Remove.send().expect( "Failed to send data to ueberzug" );
+
+				Command::new( "mpv" )
+					.arg( "--fullscreen" )
+					.args( videos.iter().map( | v | v.url() ) )
+					.spawn()
+					.expect( "Failed to start mpv" )
+					.wait()
+					.expect( "Failed to wait for mpv" );
+
+				for video in &videos {
+					mark_watched( &video.id );
+				}
+			}
 		} else {
 			break;
 		};